@@ -0,0 +1,202 @@
+use base64;
+use mpl_bubblegum::instructions::{Burn, BurnInstructionArgs};
+use solana_client::rpc_client::RpcClient;
+use solana_program::{instruction::AccountMeta as ProgramAccountMeta, pubkey::Pubkey as ProgramPubkey};
+use solana_sdk::{
+    bs58,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey as SdkPubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use std::str::FromStr;
+
+use crate::das;
+use crate::utils;
+use crate::utils::decode_proof;
+use crate::versioned;
+
+/// Permanently burns a compressed NFT leaf, removing it from the tree. See
+/// the `das` module docs for how the proof/hash fields are resolved;
+/// `leaf_owner` defaults to the payer and `leaf_delegate` to the owner.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_builder(
+    payer_secret_key: String,
+    asset_id: Option<String>,
+    leaf_owner: Option<String>,
+    leaf_delegate: Option<String>,
+    nonce: Option<u64>,
+    data_hash: Option<String>,
+    creator_hash: Option<String>,
+    root: Option<String>,
+    proof: Option<Vec<String>>,
+    merkle_tree: Option<String>,
+    index: Option<u32>,
+    das_get_asset_proof: Option<String>,
+    das_get_asset: Option<String>,
+    lookup_table_addresses: Option<Vec<String>>,
+    rpc_url: String,
+) -> Result<String, String> {
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
+
+    let secret_key_bytes = bs58::decode(payer_secret_key)
+        .into_vec()
+        .map_err(|e| format!("Failed to decode secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
+
+    let leaf_owner_pubkey = match leaf_owner {
+        Some(key) => SdkPubkey::from_str(&key).map_err(|_| "Invalid leaf_owner pubkey string".to_string())?,
+        None => payer.pubkey(),
+    };
+    let leaf_owner_program = ProgramPubkey::new_from_array(leaf_owner_pubkey.to_bytes());
+
+    let delegate_is_signing = leaf_delegate.is_some();
+    let leaf_delegate_pubkey = match leaf_delegate {
+        Some(key) => SdkPubkey::from_str(&key).map_err(|_| "Invalid leaf_delegate pubkey string".to_string())?,
+        None => leaf_owner_pubkey,
+    };
+    let leaf_delegate_program = ProgramPubkey::new_from_array(leaf_delegate_pubkey.to_bytes());
+
+    let das_data = match (&das_get_asset_proof, &das_get_asset) {
+        (Some(proof_url), Some(asset_url)) => {
+            let id = asset_id
+                .as_deref()
+                .ok_or_else(|| "asset_id is required to query a DAS endpoint".to_string())?;
+            Some(das::fetch_asset_data(proof_url, asset_url, id)?)
+        }
+        _ => None,
+    };
+
+    let proof_nodes: Vec<[u8; 32]> = match proof {
+        Some(proof_data) => decode_proof(proof_data)?,
+        None => match &das_data {
+            Some(data) => data.proof.clone(),
+            None => return Err("proof is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
+    };
+    let proof_accounts_program: Vec<ProgramAccountMeta> = proof_nodes
+        .iter()
+        .map(|hash| ProgramAccountMeta {
+            pubkey: ProgramPubkey::new_from_array(*hash),
+            is_signer: false,
+            is_writable: false,
+        })
+        .collect();
+
+    let root_bytes: [u8; 32] = match root {
+        Some(r) => bs58::decode(&r)
+            .into_vec()
+            .map_err(|_| "Invalid root string".to_string())?
+            .try_into()
+            .map_err(|_| "Invalid root length".to_string())?,
+        None => match &das_data {
+            Some(data) => data.root,
+            None => return Err("root is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
+    };
+
+    let data_hash_bytes: [u8; 32] = match data_hash {
+        Some(dh) => bs58::decode(&dh)
+            .into_vec()
+            .map_err(|_| "Invalid data_hash string".to_string())?
+            .try_into()
+            .map_err(|_| "Invalid data_hash length".to_string())?,
+        None => match &das_data {
+            Some(data) => data.data_hash,
+            None => return Err("data_hash is required (or das_get_asset + asset_id)".to_string()),
+        },
+    };
+
+    let creator_hash_bytes: [u8; 32] = match creator_hash {
+        Some(ch) => bs58::decode(&ch)
+            .into_vec()
+            .map_err(|_| "Invalid creator_hash string".to_string())?
+            .try_into()
+            .map_err(|_| "Invalid creator_hash length".to_string())?,
+        None => match &das_data {
+            Some(data) => data.creator_hash,
+            None => return Err("creator_hash is required (or das_get_asset + asset_id)".to_string()),
+        },
+    };
+
+    let nonce_value = match nonce {
+        Some(n) => n,
+        None => match &das_data {
+            Some(data) => data.nonce,
+            None => return Err("nonce is required (or das_get_asset + asset_id)".to_string()),
+        },
+    };
+
+    let index_value = match index {
+        Some(idx) => idx,
+        None => match &das_data {
+            Some(data) => data.index,
+            None => return Err("index is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
+    };
+
+    let merkle_tree_pubkey = match merkle_tree {
+        Some(mt) => SdkPubkey::from_str(&mt).map_err(|_| "Invalid merkle_tree pubkey string".to_string())?,
+        None => match &das_data {
+            Some(data) => SdkPubkey::from_str(&data.tree_id)
+                .map_err(|_| "DAS returned an invalid tree_id".to_string())?,
+            None => return Err("merkle_tree is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
+    };
+    let merkle_tree_program = ProgramPubkey::new_from_array(merkle_tree_pubkey.to_bytes());
+
+    let tree_config_program = mpl_bubblegum::accounts::TreeConfig::find_pda(&merkle_tree_program).0;
+
+    let burn_ix = Burn::new()
+        .tree_config(tree_config_program)
+        .leaf_owner(leaf_owner_program, !delegate_is_signing)
+        .leaf_delegate(leaf_delegate_program, delegate_is_signing)
+        .merkle_tree(merkle_tree_program)
+        .add_remaining_accounts(&proof_accounts_program)
+        .instruction(BurnInstructionArgs {
+            root: root_bytes,
+            data_hash: data_hash_bytes,
+            creator_hash: creator_hash_bytes,
+            nonce: nonce_value,
+            index: index_value,
+        });
+
+    let sdk_ix = Instruction {
+        program_id: SdkPubkey::new_from_array(burn_ix.program_id.to_bytes()),
+        accounts: burn_ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: SdkPubkey::new_from_array(meta.pubkey.to_bytes()),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: burn_ix.data,
+    };
+
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| SdkPubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        return versioned::build_versioned_transaction(&client, &[&payer], &[sdk_ix], &lookup_tables);
+    }
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    let sdk_recent_blockhash = solana_sdk::hash::Hash::new_from_array(recent_blockhash.to_bytes());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sdk_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        sdk_recent_blockhash,
+    );
+
+    let serialized_tx = bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok(base64::encode(serialized_tx))
+}
@@ -0,0 +1,166 @@
+use base64;
+use mpl_bubblegum::instructions::{DecompressV1, DecompressV1InstructionArgs};
+use mpl_bubblegum::types::{Creator, TokenProgramVersion, TokenStandard};
+use solana_client::rpc_client::RpcClient;
+use solana_program::pubkey::Pubkey as ProgramPubkey;
+use solana_sdk::{
+    bs58,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey as SdkPubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::rent,
+    transaction::Transaction,
+};
+use std::str::FromStr;
+
+use crate::utils;
+use crate::versioned;
+
+const TOKEN_METADATA_PROGRAM_ID: SdkPubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+const TOKEN_PROGRAM_ID: SdkPubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const ASSOCIATED_TOKEN_PROGRAM_ID: SdkPubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Turns a redeemed leaf's `voucher` back into a regular, uncompressed SPL
+/// token mint, following the same pattern as the other post-mint builders:
+/// the caller supplies the leaf's `nonce`/`name`/`symbol`/`uri`/`creators`
+/// exactly as they were when minted, since `decompress_v1` re-hashes this
+/// `MetadataArgs` on-chain and compares it against the hash stored in the
+/// voucher. `creators` must be the asset's real creators list (each entry is
+/// `(address, verified, share)`) — it is not safe to assume the caller is
+/// the original creator, since the leaf may have been transferred or
+/// delegated since it was minted.
+#[allow(clippy::too_many_arguments)]
+pub fn decompress_v1_builder(
+    payer_secret_key: String,
+    merkle_tree: String,
+    nonce: u64,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Vec<(String, bool, u8)>,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
+
+    let secret_key_bytes = bs58::decode(payer_secret_key)
+        .into_vec()
+        .map_err(|e| format!("Failed to decode secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
+
+    let merkle_tree_pubkey =
+        SdkPubkey::from_str(&merkle_tree).map_err(|_| "Invalid merkle_tree pubkey string".to_string())?;
+    let merkle_tree_program = ProgramPubkey::new_from_array(merkle_tree_pubkey.to_bytes());
+
+    let bubblegum_program_id = ProgramPubkey::new_from_array(mpl_bubblegum::ID.to_bytes());
+
+    let (voucher, _) = ProgramPubkey::find_program_address(
+        &[b"voucher", merkle_tree_program.as_ref(), &nonce.to_le_bytes()],
+        &bubblegum_program_id,
+    );
+    let (mint, _) = ProgramPubkey::find_program_address(
+        &[b"asset", merkle_tree_program.as_ref(), &nonce.to_le_bytes()],
+        &bubblegum_program_id,
+    );
+    let (mint_authority, _) = ProgramPubkey::find_program_address(&[b"mint_authority", mint.as_ref()], &bubblegum_program_id);
+
+    let token_metadata_program = ProgramPubkey::new_from_array(TOKEN_METADATA_PROGRAM_ID.to_bytes());
+    let (metadata, _) = ProgramPubkey::find_program_address(
+        &[b"metadata", token_metadata_program.as_ref(), mint.as_ref()],
+        &token_metadata_program,
+    );
+    let (master_edition, _) = ProgramPubkey::find_program_address(
+        &[b"metadata", token_metadata_program.as_ref(), mint.as_ref(), b"edition"],
+        &token_metadata_program,
+    );
+
+    let creators = creators
+        .into_iter()
+        .map(|(address, verified, share)| {
+            let address = SdkPubkey::from_str(&address).map_err(|_| "Invalid creator pubkey string".to_string())?;
+            Ok(Creator {
+                address: ProgramPubkey::new_from_array(address.to_bytes()),
+                verified,
+                share,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let leaf_owner_program = ProgramPubkey::new_from_array(payer.pubkey().to_bytes());
+    let token_program_id = ProgramPubkey::new_from_array(TOKEN_PROGRAM_ID.to_bytes());
+    let associated_token_program_id = ProgramPubkey::new_from_array(ASSOCIATED_TOKEN_PROGRAM_ID.to_bytes());
+    let (token_account, _) = ProgramPubkey::find_program_address(
+        &[leaf_owner_program.as_ref(), token_program_id.as_ref(), mint.as_ref()],
+        &associated_token_program_id,
+    );
+
+    let metadata_args = mpl_bubblegum::types::MetadataArgs {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: None,
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators,
+    };
+
+    let decompress_ix = DecompressV1::new()
+        .voucher(voucher)
+        .leaf_owner(leaf_owner_program, true)
+        .token_account(token_account)
+        .mint(mint)
+        .mint_authority(mint_authority)
+        .metadata(metadata)
+        .master_edition(master_edition)
+        .token_program(token_program_id)
+        .associated_token_program(associated_token_program_id)
+        .token_metadata_program(token_metadata_program)
+        .sysvar_rent(ProgramPubkey::new_from_array(rent::ID.to_bytes()))
+        .instruction(DecompressV1InstructionArgs { metadata: metadata_args });
+
+    let sdk_ix = Instruction {
+        program_id: SdkPubkey::new_from_array(decompress_ix.program_id.to_bytes()),
+        accounts: decompress_ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: SdkPubkey::new_from_array(meta.pubkey.to_bytes()),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: decompress_ix.data,
+    };
+
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| SdkPubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        return versioned::build_versioned_transaction(&client, &[&payer], &[sdk_ix], &lookup_tables);
+    }
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    let sdk_recent_blockhash = solana_sdk::hash::Hash::new_from_array(recent_blockhash.to_bytes());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[sdk_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        sdk_recent_blockhash,
+    );
+
+    let serialized_tx = bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok(base64::encode(serialized_tx))
+}
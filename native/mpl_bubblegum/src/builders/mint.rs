@@ -1,7 +1,7 @@
 use base64;
 use mpl_bubblegum::{
-    instructions::{MintV1, MintV1InstructionArgs},
-    types::{Creator, TokenProgramVersion, TokenStandard},
+    instructions::{MintToCollectionV1, MintToCollectionV1InstructionArgs, MintV1, MintV1InstructionArgs},
+    types::{Collection, Creator, TokenProgramVersion, TokenStandard},
 };
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -14,6 +14,12 @@ use solana_sdk::{
     system_program,
     transaction::Transaction,
 };
+use std::str::FromStr;
+
+use crate::utils;
+use crate::versioned;
+
+const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
 pub fn mint_v1_builder(
     payer_secret_key: String,
@@ -23,14 +29,15 @@ pub fn mint_v1_builder(
     uri: String,
     seller_fee_basis_points: u16,
     share: u8,
-) -> String {
-    let rpc_url = "https://api.devnet.solana.com".to_string();
-    let client = RpcClient::new(rpc_url);
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
     let secret_key_bytes = bs58::decode(payer_secret_key)
         .into_vec()
-        .expect("Error: Failed to decode the secret key.");
-    let payer = Keypair::from_bytes(&secret_key_bytes).expect("Error: Invalid secret key.");
-    let merkle_tree = Pubkey::from_str_const(&merkle_tree);
+        .map_err(|e| format!("Failed to decode secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
+    let merkle_tree = Pubkey::from_str(&merkle_tree).map_err(|_| "Invalid merkle_tree pubkey string".to_string())?;
     let (tree_config, _) = Pubkey::find_program_address(
         &[merkle_tree.as_array()],
         &pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"),
@@ -83,13 +90,158 @@ pub fn mint_v1_builder(
             .collect(),
         data: mint_ix.data,
     };
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| Pubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        return versioned::build_versioned_transaction(&client, &[&payer], &[mint_ix], &lookup_tables);
+    }
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
     let tx = Transaction::new_signed_with_payer(
         &[mint_ix],
         Some(&payer.pubkey()),
         &[&payer],
         recent_blockhash.to_bytes().into(),
     );
-    let serialized_tx = bincode::serialize(&tx).expect("Error: Failed to serialize the transaction");
-    base64::encode(serialized_tx)
-}
\ No newline at end of file
+    let serialized_tx = bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok(base64::encode(serialized_tx))
+}
+
+/// Mints a compressed NFT directly into a verified Metaplex collection,
+/// instead of leaving `MetadataArgs.collection` empty like `mint_v1_builder`
+/// does. The collection authority must co-sign, so its secret key is taken
+/// separately from the payer's.
+pub fn mint_to_collection_v1_builder(
+    payer_secret_key: String,
+    collection_authority_secret_key: String,
+    merkle_tree: String,
+    collection_mint: String,
+    collection_metadata: String,
+    collection_edition: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    share: u8,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
+    let secret_key_bytes = bs58::decode(payer_secret_key)
+        .into_vec()
+        .map_err(|e| format!("Failed to decode secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
+
+    let collection_authority_secret_key_bytes = bs58::decode(collection_authority_secret_key)
+        .into_vec()
+        .map_err(|e| format!("Failed to decode collection authority secret key: {}", e))?;
+    let collection_authority = Keypair::from_bytes(&collection_authority_secret_key_bytes)
+        .map_err(|e| format!("Not a valid collection authority secret key: {}", e))?;
+
+    let merkle_tree = Pubkey::from_str(&merkle_tree).map_err(|_| "Invalid merkle_tree pubkey string".to_string())?;
+    let collection_mint =
+        Pubkey::from_str(&collection_mint).map_err(|_| "Invalid collection_mint pubkey string".to_string())?;
+    let collection_metadata = Pubkey::from_str(&collection_metadata)
+        .map_err(|_| "Invalid collection_metadata pubkey string".to_string())?;
+    let collection_edition = Pubkey::from_str(&collection_edition)
+        .map_err(|_| "Invalid collection_edition pubkey string".to_string())?;
+
+    let (tree_config, _) = Pubkey::find_program_address(
+        &[merkle_tree.as_array()],
+        &pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"),
+    );
+    let (bubblegum_signer, _) = Pubkey::find_program_address(
+        &[b"collection_cpi"],
+        &pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"),
+    );
+
+    let mint_ix_accounts = mpl_bubblegum::types::MetadataArgs {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: Some(Collection {
+            key: collection_mint.to_bytes().into(),
+            verified: true,
+        }),
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![Creator {
+            address: payer.pubkey().to_bytes().into(),
+            verified: true,
+            share,
+        }],
+    };
+
+    let mint_ix = MintToCollectionV1 {
+        tree_config: tree_config.to_bytes().into(),
+        leaf_owner: payer.pubkey().to_bytes().into(),
+        leaf_delegate: payer.pubkey().to_bytes().into(),
+        merkle_tree: merkle_tree.to_bytes().into(),
+        payer: payer.pubkey().to_bytes().into(),
+        tree_creator_or_delegate: payer.pubkey().to_bytes().into(),
+        collection_authority: collection_authority.pubkey().to_bytes().into(),
+        collection_authority_record_pda: None,
+        collection_mint: collection_mint.to_bytes().into(),
+        collection_metadata: collection_metadata.to_bytes().into(),
+        edition: collection_edition.to_bytes().into(),
+        bubblegum_signer: bubblegum_signer.to_bytes().into(),
+        log_wrapper: pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV")
+            .to_bytes()
+            .into(),
+        compression_program: spl_account_compression::ID.to_bytes().into(),
+        token_metadata_program: TOKEN_METADATA_PROGRAM_ID.to_bytes().into(),
+        system_program: system_program::ID.to_bytes().into(),
+    };
+    let mint_ix = mint_ix.instruction(MintToCollectionV1InstructionArgs {
+        metadata: mint_ix_accounts,
+    });
+    let mint_ix = Instruction {
+        program_id: mint_ix.program_id.to_bytes().into(),
+        accounts: mint_ix
+            .accounts
+            .iter()
+            .map(|meta| AccountMeta {
+                pubkey: meta.pubkey.to_bytes().into(),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: mint_ix.data,
+    };
+
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| Pubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        return versioned::build_versioned_transaction(
+            &client,
+            &[&payer, &collection_authority],
+            &[mint_ix],
+            &lookup_tables,
+        );
+    }
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    let tx = Transaction::new_signed_with_payer(
+        &[mint_ix],
+        Some(&payer.pubkey()),
+        &[&payer, &collection_authority],
+        recent_blockhash.to_bytes().into(),
+    );
+    let serialized_tx = bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok(base64::encode(serialized_tx))
+}
@@ -0,0 +1,7 @@
+pub mod tree;
+pub mod mint;
+pub mod transfer;
+pub mod burn;
+pub mod delegate;
+pub mod redeem;
+pub mod decompress;
@@ -15,6 +15,8 @@ use solana_sdk::{
 };
 use std::str::FromStr;
 
+use crate::das;
+use crate::utils;
 use crate::utils::decode_proof;
 
 pub fn transfer_builder(
@@ -33,9 +35,10 @@ pub fn transfer_builder(
     leaf_delegate: Option<String>,
     das_get_asset_proof: Option<String>,
     das_get_asset: Option<String>,
+    lookup_table_addresses: Option<Vec<String>>,
+    rpc_url: String,
 ) -> Result<String, String> {
-    let rpc_url = "https://api.devnet.solana.com".to_string();
-    let client = RpcClient::new(rpc_url);
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
     
     // Parse the payer keypair
     let secret_key_bytes = bs58::decode(payer_secret_key)
@@ -51,42 +54,59 @@ pub fn transfer_builder(
 
     // Determine who is signing
     let delegate_is_signing = leaf_delegate.is_some();
-    
-    // Setup leaf owner and leaf delegate
+
+    // If the caller didn't hand-assemble the proof/hashes/owner/delegate,
+    // fetch them from a DAS endpoint using just the asset id.
+    let das_data = match (&das_get_asset_proof, &das_get_asset) {
+        (Some(proof_url), Some(asset_url)) => {
+            let id = asset_id
+                .as_deref()
+                .ok_or_else(|| "asset_id is required to query a DAS endpoint".to_string())?;
+            Some(das::fetch_asset_data(proof_url, asset_url, id)?)
+        }
+        _ => None,
+    };
+
+    // Setup leaf owner and leaf delegate. Falling back to the DAS-resolved
+    // owner (rather than the payer) lets a delegate invoke this on behalf of
+    // someone else, by passing `leaf_delegate` explicitly and letting
+    // `leaf_owner` resolve from the chain.
     let leaf_owner_pubkey = match leaf_owner {
         Some(key) => SdkPubkey::from_str(&key)
             .map_err(|_| "Invalid leaf_owner pubkey string".to_string())?,
-        None => payer.pubkey(),
+        None => match &das_data {
+            Some(data) => data.owner,
+            None => payer.pubkey(),
+        },
     };
     let leaf_owner_program = ProgramPubkey::new_from_array(leaf_owner_pubkey.to_bytes());
-    
+
     let leaf_delegate_pubkey = match leaf_delegate {
         Some(key) => SdkPubkey::from_str(&key)
             .map_err(|_| "Invalid leaf_delegate pubkey string".to_string())?,
-        None => leaf_owner_pubkey,
+        None => match &das_data {
+            Some(data) => data.delegate.unwrap_or(leaf_owner_pubkey),
+            None => leaf_owner_pubkey,
+        },
     };
     let leaf_delegate_program = ProgramPubkey::new_from_array(leaf_delegate_pubkey.to_bytes());
 
-    // Process proof - assuming decode_proof handles the parsing of proof strings
-    let proof_vec = match proof {
-        Some(proof_data) => proof_data,
-        None => {
-            if let Some(_) = das_get_asset_proof {
-                // In a real implementation, you would parse the JSON here
-                // For now, just return an error since we need the proof
-                return Err("Proof extraction from DAS response not implemented".to_string());
-            } else {
-                return Err("proof is required".to_string());
-            }
+    // Process proof - either the caller's own list, or the DAS proof (which
+    // already has any canopy nodes stripped, so it's used as-is).
+    let proof_nodes: Vec<[u8; 32]> = match proof {
+        Some(proof_data) => decode_proof(proof_data)?,
+        None => match &das_data {
+            Some(data) => data.proof.clone(),
+            None => return Err("proof is required (or das_get_asset_proof + asset_id)".to_string()),
         },
     };
 
-    let proof_accounts: Vec<AccountMeta> = decode_proof(proof_vec.clone())
+    let proof_accounts: Vec<AccountMeta> = proof_nodes
         .iter()
         .map(|hash| AccountMeta::new_readonly(SdkPubkey::new_from_array(*hash), false))
         .collect();
 
-    let proof_accounts_program: Vec<ProgramAccountMeta> = decode_proof(proof_vec)
+    let proof_accounts_program: Vec<ProgramAccountMeta> = proof_nodes
         .iter()
         .map(|hash| ProgramAccountMeta {
             pubkey: ProgramPubkey::new_from_array(*hash),
@@ -102,9 +122,9 @@ pub fn transfer_builder(
             .map_err(|_| "Invalid root string".to_string())?
             .try_into()
             .map_err(|_| "Invalid root length".to_string())?,
-        None => {
-            // In a real implementation, you would parse the JSON here
-            return Err("root is required".to_string());
+        None => match &das_data {
+            Some(data) => data.root,
+            None => return Err("root is required (or das_get_asset_proof + asset_id)".to_string()),
         },
     };
 
@@ -115,9 +135,9 @@ pub fn transfer_builder(
             .map_err(|_| "Invalid data_hash string".to_string())?
             .try_into()
             .map_err(|_| "Invalid data_hash length".to_string())?,
-        None => {
-            // In a real implementation, you would parse the JSON here
-            return Err("data_hash is required".to_string());
+        None => match &das_data {
+            Some(data) => data.data_hash,
+            None => return Err("data_hash is required (or das_get_asset + asset_id)".to_string()),
         },
     };
 
@@ -128,29 +148,39 @@ pub fn transfer_builder(
             .map_err(|_| "Invalid creator_hash string".to_string())?
             .try_into()
             .map_err(|_| "Invalid creator_hash length".to_string())?,
-        None => {
-            // In a real implementation, you would parse the JSON here
-            return Err("creator_hash is required".to_string());
+        None => match &das_data {
+            Some(data) => data.creator_hash,
+            None => return Err("creator_hash is required (or das_get_asset + asset_id)".to_string()),
         },
     };
 
     // Get nonce (leaf_id)
     let nonce = match leaf_id {
         Some(id) => id,
-        None => return Err("leaf_id is required".to_string()),
+        None => match &das_data {
+            Some(data) => data.nonce,
+            None => return Err("leaf_id is required (or das_get_asset + asset_id)".to_string()),
+        },
     };
 
     // Get index
     let index_value = match index {
         Some(idx) => idx,
-        None => return Err("index is required".to_string()),
+        None => match &das_data {
+            Some(data) => data.index,
+            None => return Err("index is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
     };
 
     // Get merkle tree
     let merkle_tree_pubkey = match merkle_tree {
         Some(mt) => SdkPubkey::from_str(&mt)
             .map_err(|_| "Invalid merkle_tree pubkey string".to_string())?,
-        None => return Err("merkle_tree is required".to_string()),
+        None => match &das_data {
+            Some(data) => SdkPubkey::from_str(&data.tree_id)
+                .map_err(|_| "DAS returned an invalid tree_id".to_string())?,
+            None => return Err("merkle_tree is required (or das_get_asset_proof + asset_id)".to_string()),
+        },
     };
     let merkle_tree_program = ProgramPubkey::new_from_array(merkle_tree_pubkey.to_bytes());
 
@@ -193,6 +223,19 @@ pub fn transfer_builder(
         data: transfer_ix.data,
     };
 
+    // A deep proof (one remaining account per non-canopy tree level) plus the fixed accounts can
+    // push a legacy transaction over the 1232-byte packet limit, so callers
+    // with large trees can opt into a v0 transaction backed by Address
+    // Lookup Tables instead.
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| SdkPubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = crate::versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        return crate::versioned::build_versioned_transaction(&client, &[&payer], &[sdk_ix], &lookup_tables);
+    }
+
     // Create the transaction
     let recent_blockhash = client.get_latest_blockhash()
         .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
@@ -210,6 +253,6 @@ pub fn transfer_builder(
     // Serialize the transaction
     let serialized_tx = bincode::serialize(&tx)
         .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
-    
+
     Ok(base64::encode(serialized_tx))
 }
\ No newline at end of file
@@ -12,24 +12,89 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use spl_account_compression::{state::CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1, ConcurrentMerkleTree};
+use std::mem::size_of;
+use std::str::FromStr;
 
-pub fn create_tree_config_builder(payer_secret_key: String) -> Vec<String> {
-    const MAX_DEPTH: usize = 14;
-    const MAX_BUFFER_SIZE: usize = 64;
+use crate::utils;
+use crate::versioned;
+
+/// `ConcurrentMerkleTree` is generic over `(max_depth, max_buffer_size)`, so
+/// only the pairs the on-chain program actually instantiates can be sized at
+/// runtime. Unsupported pairs return a descriptive error instead of a panic.
+macro_rules! merkle_tree_account_size {
+    ($max_depth:expr, $max_buffer_size:expr) => {
+        match ($max_depth, $max_buffer_size) {
+            (3, 8) => Ok(size_of::<ConcurrentMerkleTree<3, 8>>()),
+            (5, 8) => Ok(size_of::<ConcurrentMerkleTree<5, 8>>()),
+            (14, 64) => Ok(size_of::<ConcurrentMerkleTree<14, 64>>()),
+            (14, 256) => Ok(size_of::<ConcurrentMerkleTree<14, 256>>()),
+            (14, 1024) => Ok(size_of::<ConcurrentMerkleTree<14, 1024>>()),
+            (14, 2048) => Ok(size_of::<ConcurrentMerkleTree<14, 2048>>()),
+            (15, 64) => Ok(size_of::<ConcurrentMerkleTree<15, 64>>()),
+            (16, 64) => Ok(size_of::<ConcurrentMerkleTree<16, 64>>()),
+            (17, 64) => Ok(size_of::<ConcurrentMerkleTree<17, 64>>()),
+            (18, 64) => Ok(size_of::<ConcurrentMerkleTree<18, 64>>()),
+            (18, 1024) => Ok(size_of::<ConcurrentMerkleTree<18, 1024>>()),
+            (19, 64) => Ok(size_of::<ConcurrentMerkleTree<19, 64>>()),
+            (20, 64) => Ok(size_of::<ConcurrentMerkleTree<20, 64>>()),
+            (20, 256) => Ok(size_of::<ConcurrentMerkleTree<20, 256>>()),
+            (20, 1024) => Ok(size_of::<ConcurrentMerkleTree<20, 1024>>()),
+            (20, 2048) => Ok(size_of::<ConcurrentMerkleTree<20, 2048>>()),
+            (24, 64) => Ok(size_of::<ConcurrentMerkleTree<24, 64>>()),
+            (24, 256) => Ok(size_of::<ConcurrentMerkleTree<24, 256>>()),
+            (24, 512) => Ok(size_of::<ConcurrentMerkleTree<24, 512>>()),
+            (24, 1024) => Ok(size_of::<ConcurrentMerkleTree<24, 1024>>()),
+            (24, 2048) => Ok(size_of::<ConcurrentMerkleTree<24, 2048>>()),
+            (26, 512) => Ok(size_of::<ConcurrentMerkleTree<26, 512>>()),
+            (26, 1024) => Ok(size_of::<ConcurrentMerkleTree<26, 1024>>()),
+            (26, 2048) => Ok(size_of::<ConcurrentMerkleTree<26, 2048>>()),
+            (30, 512) => Ok(size_of::<ConcurrentMerkleTree<30, 512>>()),
+            (30, 1024) => Ok(size_of::<ConcurrentMerkleTree<30, 1024>>()),
+            (30, 2048) => Ok(size_of::<ConcurrentMerkleTree<30, 2048>>()),
+            (depth, buffer) => Err(format!(
+                "Unsupported (max_depth, max_buffer_size) pair: ({}, {}). \
+                 See the spl-account-compression valid depth/buffer combinations.",
+                depth, buffer
+            )),
+        }
+    };
+}
+
+pub fn create_tree_config_builder(
+    payer_secret_key: String,
+    max_depth: u32,
+    max_buffer_size: u32,
+    canopy_depth: u32,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<Vec<String>, String> {
     let secret_key_bytes = bs58::decode(payer_secret_key)
         .into_vec()
-        .expect("Error: Failed to decode the secret key.");
-    let payer = Keypair::from_bytes(&secret_key_bytes).expect("Error: Invalid secret key.");
+        .map_err(|e| format!("Failed to decode secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
     let merkle_tree = Keypair::new();
     let (tree_config, _) = Pubkey::find_program_address(
         &[merkle_tree.pubkey().as_array()],
         &pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"),
     );
-    let rpc_url = "https://api.devnet.solana.com".to_string();
-    let client = RpcClient::new(rpc_url);
-    let size = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1
-        + std::mem::size_of::<ConcurrentMerkleTree<MAX_DEPTH, MAX_BUFFER_SIZE>>();
-    let rent = client.get_minimum_balance_for_rent_exemption(size).unwrap();
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
+
+    let tree_size: usize = merkle_tree_account_size!(max_depth, max_buffer_size)?;
+    // A canopy caches the top `canopy_depth` levels of the tree on-chain, so
+    // composability programs don't need the full proof for every operation.
+    // It can't cache more levels than the tree has, and the 2^(depth+1) below
+    // would overflow for anything absurdly large anyway.
+    if canopy_depth >= max_depth {
+        return Err(format!(
+            "canopy_depth ({}) must be less than max_depth ({})",
+            canopy_depth, max_depth
+        ));
+    }
+    let canopy_size: u64 = 32 * (2u64.pow(canopy_depth + 1) - 2);
+    let size = CONCURRENT_MERKLE_TREE_HEADER_SIZE_V1 + tree_size + canopy_size as usize;
+    let rent = client
+        .get_minimum_balance_for_rent_exemption(size)
+        .map_err(|e| format!("Failed to fetch rent exemption amount: {}", e))?;
     let create_merkle_ix: Instruction = system_instruction::create_account(
         &payer.pubkey().to_bytes().into(),
         &merkle_tree.pubkey().to_bytes().into(),
@@ -38,8 +103,8 @@ pub fn create_tree_config_builder(payer_secret_key: String) -> Vec<String> {
         &spl_account_compression::ID.to_bytes().into(),
     );
     let create_tree_accounts = CreateTreeConfigInstructionArgs {
-        max_depth: MAX_DEPTH as u32,
-        max_buffer_size: MAX_BUFFER_SIZE as u32,
+        max_depth,
+        max_buffer_size,
         public: Some(false),
     };
     let create_config_ix = CreateTreeConfig {
@@ -67,16 +132,62 @@ pub fn create_tree_config_builder(payer_secret_key: String) -> Vec<String> {
             .collect(),
         data: create_config_ix.data,
     };
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
+    if let Some(table_addresses) = lookup_table_addresses {
+        let table_pubkeys = table_addresses
+            .iter()
+            .map(|address| Pubkey::from_str(address).map_err(|_| "Invalid lookup table pubkey string".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_tables = versioned::resolve_lookup_tables(&client, &table_pubkeys)?;
+        let serialized_tx = versioned::build_versioned_transaction(
+            &client,
+            &[&payer, &merkle_tree],
+            &[create_merkle_ix, create_config_ix],
+            &lookup_tables,
+        )?;
+        return Ok(vec![serialized_tx, merkle_tree.pubkey().to_string()]);
+    }
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
     let tx = Transaction::new_signed_with_payer(
         &[create_merkle_ix, create_config_ix],
         Some(&payer.pubkey()),
         &[&merkle_tree, &payer],
         recent_blockhash.to_bytes().into(),
     );
-    let serialized_tx = bincode::serialize(&tx).expect("Error: Failed to serialize the transaction.");
-    vec![
+    let serialized_tx = bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok(vec![
         base64::encode(serialized_tx),
         merkle_tree.pubkey().to_string(),
-    ]
+    ])
+}
+
+/// Builds the `create_lookup_table` + `extend_lookup_table` instructions for
+/// `addresses` so callers can create the Address Lookup Table a deep-proof
+/// transfer will reference before requesting a versioned transaction.
+pub fn create_lookup_table_builder(
+    payer_secret_key: String,
+    recent_slot: u64,
+    addresses: Vec<String>,
+    rpc_url: String,
+) -> Result<Vec<String>, String> {
+    let client = RpcClient::new(utils::resolve_rpc_url(&rpc_url));
+
+    let secret_key_bytes = bs58::decode(payer_secret_key)
+        .into_vec()
+        .map_err(|e| format!("Failed to decode the secret key: {}", e))?;
+    let payer = Keypair::from_bytes(&secret_key_bytes).map_err(|e| format!("Not a valid secret key: {}", e))?;
+
+    let addresses: Vec<Pubkey> = addresses
+        .iter()
+        .map(|address| {
+            Pubkey::from_str(address).map_err(|_| format!("Invalid address pubkey string: {}", address))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (serialized_tx, table_address) =
+        versioned::create_lookup_table_transaction(&client, &payer, recent_slot, addresses)?;
+
+    Ok(vec![serialized_tx, table_address.to_string()])
 }
\ No newline at end of file
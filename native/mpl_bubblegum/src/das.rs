@@ -0,0 +1,150 @@
+//! Resolves the fields a transfer/burn/delegate/redeem instruction needs —
+//! `root`/`proof`/`data_hash`/`creator_hash`/`nonce`/`index`/`tree_id` —
+//! from the DAS `getAssetProof`/`getAsset` endpoints, for builders whose
+//! caller only has an `asset_id`. Each such builder also accepts these fields
+//! directly, falling back to [`fetch_asset_data`] only when a given field is
+//! `None`. The DAS `owner`/`delegate` are used only by the transfer builder;
+//! the burn, delegate and redeem builders default the leaf owner to the payer
+//! and the leaf delegate to the owner.
+
+use serde::Deserialize;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+use crate::utils::decode_proof;
+
+const JSONRPC_VERSION: &str = "2.0";
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+struct AssetProofResult {
+    root: String,
+    proof: Vec<String>,
+    node_index: u64,
+    tree_id: String,
+}
+
+#[derive(Deserialize)]
+struct AssetResult {
+    compression: CompressionInfo,
+    ownership: OwnershipInfo,
+}
+
+#[derive(Deserialize)]
+struct CompressionInfo {
+    data_hash: String,
+    creator_hash: String,
+    leaf_id: u64,
+}
+
+#[derive(Deserialize)]
+struct OwnershipInfo {
+    owner: String,
+    delegate: Option<String>,
+}
+
+/// The pieces of a transfer/burn/delegate instruction that can be recovered
+/// from the DAS `getAssetProof`/`getAsset` endpoints instead of being
+/// hand-assembled by the caller, including the current `owner`/`delegate` so
+/// a delegate-initiated call can be resolved from just an `asset_id`.
+pub struct DasAssetData {
+    pub root: [u8; 32],
+    pub proof: Vec<[u8; 32]>,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    pub tree_id: String,
+    pub owner: Pubkey,
+    pub delegate: Option<Pubkey>,
+}
+
+fn call<T: for<'de> Deserialize<'de>>(url: &str, method: &str, asset_id: &str) -> Result<T, String> {
+    let body = json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": "mpl-bubblegum-ex",
+        "method": method,
+        "params": { "id": asset_id },
+    });
+
+    let response = ureq::post(url)
+        .set("Content-Type", "application/json")
+        .send_json(body)
+        .map_err(|e| format!("DAS '{}' request failed: {}", method, e))?;
+
+    let raw: serde_json::Value = response
+        .into_json()
+        .map_err(|e| format!("DAS '{}' response was not valid JSON: {}", method, e))?;
+
+    if let Some(error) = raw.get("error") {
+        return Err(format!("DAS '{}' returned an error: {}", method, error));
+    }
+
+    serde_json::from_value::<JsonRpcResponse<T>>(raw)
+        .map(|r| r.result)
+        .map_err(|e| format!("DAS '{}' response had an unexpected shape: {}", method, e))
+}
+
+fn decode_hash(label: &str, value: &str) -> Result<[u8; 32], String> {
+    decode_proof(vec![value.to_string()])
+        .map_err(|e| format!("DAS returned an invalid {}: {}", label, e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("DAS did not return a {}", label))
+}
+
+/// Fetches `getAssetProof` and `getAsset` for `asset_id` and assembles the
+/// fields a transfer/burn/delegate instruction needs.
+///
+/// `proof_url` and `asset_url` are usually the same DAS endpoint, kept as
+/// separate arguments so callers can point the two RPC methods at different
+/// providers. If the tree has a canopy, the DAS response already truncates
+/// `proof` to the non-canopy nodes, so it is passed through `decode_proof`
+/// unchanged rather than padded back out.
+pub fn fetch_asset_data(proof_url: &str, asset_url: &str, asset_id: &str) -> Result<DasAssetData, String> {
+    let proof: AssetProofResult = call(proof_url, "getAssetProof", asset_id)?;
+    let asset: AssetResult = call(asset_url, "getAsset", asset_id)?;
+
+    // `node_index` numbers leaves starting at 2^max_depth, so the depth is
+    // recovered from its bit length and `index` is the remainder. `node_index`
+    // is untrusted network input, so this is done with integer/checked math
+    // rather than trusting `log2().floor()` on a float for large values.
+    if proof.node_index == 0 {
+        return Err("DAS returned a node_index of 0".to_string());
+    }
+    let max_depth = proof.node_index.ilog2();
+    let depth_start = 2u64
+        .checked_pow(max_depth)
+        .ok_or_else(|| format!("DAS returned an out-of-range node_index: {}", proof.node_index))?;
+    let index: u32 = proof
+        .node_index
+        .checked_sub(depth_start)
+        .ok_or_else(|| format!("DAS returned an out-of-range node_index: {}", proof.node_index))?
+        .try_into()
+        .map_err(|_| format!("DAS returned an out-of-range node_index: {}", proof.node_index))?;
+
+    let owner = Pubkey::from_str(&asset.ownership.owner)
+        .map_err(|_| format!("DAS returned an invalid owner: {}", asset.ownership.owner))?;
+    let delegate = asset
+        .ownership
+        .delegate
+        .map(|d| Pubkey::from_str(&d).map_err(|_| format!("DAS returned an invalid delegate: {}", d)))
+        .transpose()?;
+
+    Ok(DasAssetData {
+        root: decode_hash("root", &proof.root)?,
+        proof: decode_proof(proof.proof).map_err(|e| format!("DAS returned an invalid proof: {}", e))?,
+        data_hash: decode_hash("data_hash", &asset.compression.data_hash)?,
+        creator_hash: decode_hash("creator_hash", &asset.compression.creator_hash)?,
+        nonce: asset.compression.leaf_id,
+        index,
+        tree_id: proof.tree_id,
+        owner,
+        delegate,
+    })
+}
@@ -1,14 +1,31 @@
 use rustler::nif;
 
 mod builders;
+mod das;
 mod utils;
+mod versioned;
 
 #[nif(schedule = "DirtyIo")]
-fn create_tree_config_builder(payer_secret_key: String) -> Vec<String> {
-    builders::tree::create_tree_config_builder(payer_secret_key)
+fn create_tree_config_builder(
+    payer_secret_key: String,
+    max_depth: u32,
+    max_buffer_size: u32,
+    canopy_depth: u32,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<Vec<String>, String> {
+    builders::tree::create_tree_config_builder(
+        payer_secret_key,
+        max_depth,
+        max_buffer_size,
+        canopy_depth,
+        rpc_url,
+        lookup_table_addresses,
+    )
 }
 
 #[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
 fn mint_v1_builder(
     payer_secret_key: String,
     merkle_tree: String,
@@ -17,7 +34,9 @@ fn mint_v1_builder(
     uri: String,
     seller_fee_basis_points: u16,
     share: u8,
-) -> String {
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
     builders::mint::mint_v1_builder(
         payer_secret_key,
         merkle_tree,
@@ -26,10 +45,47 @@ fn mint_v1_builder(
         uri,
         seller_fee_basis_points,
         share,
+        rpc_url,
+        lookup_table_addresses,
     )
 }
 
 #[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+fn mint_to_collection_v1_builder(
+    payer_secret_key: String,
+    collection_authority_secret_key: String,
+    merkle_tree: String,
+    collection_mint: String,
+    collection_metadata: String,
+    collection_edition: String,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    share: u8,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    builders::mint::mint_to_collection_v1_builder(
+        payer_secret_key,
+        collection_authority_secret_key,
+        merkle_tree,
+        collection_mint,
+        collection_metadata,
+        collection_edition,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        share,
+        rpc_url,
+        lookup_table_addresses,
+    )
+}
+
+#[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_builder(
 payer_secret_key: String,
 to_address: String,
@@ -40,6 +96,8 @@ creator_hash: String,
 root: String,
 proof: Vec<String>,
 merkle_tree: String,
+rpc_url: String,
+lookup_table_addresses: Option<Vec<String>>,
 ) -> Result<String, String> {
 // Call the implementation function with appropriate Option wrappers
 builders::transfer::transfer_builder(
@@ -58,8 +116,204 @@ builders::transfer::transfer_builder(
     None,           // leaf_delegate
     None,           // das_get_asset_proof
     None,           // das_get_asset
+    lookup_table_addresses,
+    rpc_url,
 )
 }
 
+/// Builds a transfer transaction from just the payer, the recipient, and the
+/// asset id, resolving `root`/`proof`/`data_hash`/`creator_hash`/`nonce`/
+/// `index`/`merkle_tree`/`leaf_owner` via the DAS `getAssetProof` and
+/// `getAsset` endpoints at `das_url` instead of requiring the caller to
+/// assemble them. Pass `leaf_delegate` when `payer` is the asset's delegate
+/// rather than its owner.
+#[nif(schedule = "DirtyIo")]
+pub fn transfer_builder_from_asset_id(
+    payer_secret_key: String,
+    to_address: String,
+    asset_id: String,
+    das_url: String,
+    leaf_delegate: Option<String>,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    builders::transfer::transfer_builder(
+        payer_secret_key,
+        to_address,
+        Some(asset_id),
+        None,            // leaf_id - resolved from DAS
+        None,            // data_hash - resolved from DAS
+        None,            // creator_hash - resolved from DAS
+        None,            // root - resolved from DAS
+        None,            // proof - resolved from DAS
+        None,            // merkle_tree - resolved from DAS
+        None,            // tree_config
+        None,            // index - resolved from DAS
+        None,            // leaf_owner - resolved from DAS
+        leaf_delegate,   // Some(pubkey) when the payer is signing as the delegate, not the owner
+        Some(das_url.clone()), // das_get_asset_proof
+        Some(das_url),   // das_get_asset
+        lookup_table_addresses,
+        rpc_url,
+    )
+}
+
+#[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+pub fn burn_builder(
+    payer_secret_key: String,
+    asset_id: Option<String>,
+    leaf_owner: Option<String>,
+    leaf_delegate: Option<String>,
+    nonce: Option<u64>,
+    data_hash: Option<String>,
+    creator_hash: Option<String>,
+    root: Option<String>,
+    proof: Option<Vec<String>>,
+    merkle_tree: Option<String>,
+    index: Option<u32>,
+    das_get_asset_proof: Option<String>,
+    das_get_asset: Option<String>,
+    lookup_table_addresses: Option<Vec<String>>,
+    rpc_url: String,
+) -> Result<String, String> {
+    builders::burn::burn_builder(
+        payer_secret_key,
+        asset_id,
+        leaf_owner,
+        leaf_delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        root,
+        proof,
+        merkle_tree,
+        index,
+        das_get_asset_proof,
+        das_get_asset,
+        lookup_table_addresses,
+        rpc_url,
+    )
+}
+
+#[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+pub fn delegate_builder(
+    payer_secret_key: String,
+    new_leaf_delegate: String,
+    asset_id: Option<String>,
+    leaf_owner: Option<String>,
+    previous_leaf_delegate: Option<String>,
+    nonce: Option<u64>,
+    data_hash: Option<String>,
+    creator_hash: Option<String>,
+    root: Option<String>,
+    proof: Option<Vec<String>>,
+    merkle_tree: Option<String>,
+    index: Option<u32>,
+    das_get_asset_proof: Option<String>,
+    das_get_asset: Option<String>,
+    lookup_table_addresses: Option<Vec<String>>,
+    rpc_url: String,
+) -> Result<String, String> {
+    builders::delegate::delegate_builder(
+        payer_secret_key,
+        new_leaf_delegate,
+        asset_id,
+        leaf_owner,
+        previous_leaf_delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        root,
+        proof,
+        merkle_tree,
+        index,
+        das_get_asset_proof,
+        das_get_asset,
+        lookup_table_addresses,
+        rpc_url,
+    )
+}
+
+#[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+pub fn redeem_builder(
+    payer_secret_key: String,
+    asset_id: Option<String>,
+    leaf_owner: Option<String>,
+    leaf_delegate: Option<String>,
+    nonce: Option<u64>,
+    data_hash: Option<String>,
+    creator_hash: Option<String>,
+    root: Option<String>,
+    proof: Option<Vec<String>>,
+    merkle_tree: Option<String>,
+    index: Option<u32>,
+    das_get_asset_proof: Option<String>,
+    das_get_asset: Option<String>,
+    lookup_table_addresses: Option<Vec<String>>,
+    rpc_url: String,
+) -> Result<String, String> {
+    builders::redeem::redeem_builder(
+        payer_secret_key,
+        asset_id,
+        leaf_owner,
+        leaf_delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        root,
+        proof,
+        merkle_tree,
+        index,
+        das_get_asset_proof,
+        das_get_asset,
+        lookup_table_addresses,
+        rpc_url,
+    )
+}
+
+#[nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+pub fn decompress_v1_builder(
+    payer_secret_key: String,
+    merkle_tree: String,
+    nonce: u64,
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    creators: Vec<(String, bool, u8)>,
+    rpc_url: String,
+    lookup_table_addresses: Option<Vec<String>>,
+) -> Result<String, String> {
+    builders::decompress::decompress_v1_builder(
+        payer_secret_key,
+        merkle_tree,
+        nonce,
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points,
+        creators,
+        rpc_url,
+        lookup_table_addresses,
+    )
+}
+
+/// Builds the `create_lookup_table` + `extend_lookup_table` instructions for
+/// `addresses` so a caller can stand up the Address Lookup Table a
+/// deep-proof transfer will reference, before requesting a versioned
+/// transaction from the other builders.
+#[nif(schedule = "DirtyIo")]
+pub fn create_lookup_table_builder(
+    payer_secret_key: String,
+    recent_slot: u64,
+    addresses: Vec<String>,
+    rpc_url: String,
+) -> Result<Vec<String>, String> {
+    builders::tree::create_lookup_table_builder(payer_secret_key, recent_slot, addresses, rpc_url)
+}
 
-rustler::init!("Elixir.MplBubblegum");
\ No newline at end of file
+rustler::init!("Elixir.MplBubblegum");
@@ -1,31 +1,40 @@
-use solana_sdk::{bs58, pubkey::Pubkey};
+use solana_sdk::bs58;
 
-pub trait FromStrConst {
-    fn from_str_const(s: &str) -> Self;
-}
-
-impl FromStrConst for Pubkey {
-    fn from_str_const(s: &str) -> Self {
-        Pubkey::try_from(s).expect("Error: Invalid public key string.")
+/// Resolves a named cluster (`mainnet`/`mainnet-beta`, `devnet`, `testnet`,
+/// `localnet`) to its public RPC URL. Anything else is assumed to already be
+/// an RPC URL and is passed through unchanged, so callers can also point at
+/// a private/paid RPC provider.
+pub fn resolve_rpc_url(cluster_or_url: &str) -> String {
+    match cluster_or_url {
+        "mainnet" | "mainnet-beta" => "https://api.mainnet-beta.solana.com".to_string(),
+        "devnet" => "https://api.devnet.solana.com".to_string(),
+        "testnet" => "https://api.testnet.solana.com".to_string(),
+        "localnet" | "localhost" => "http://127.0.0.1:8899".to_string(),
+        other => other.to_string(),
     }
 }
 
-pub fn decode_proof(base58_strings: Vec<String>) -> Vec<[u8; 32]> {
+pub fn decode_proof(base58_strings: Vec<String>) -> Result<Vec<[u8; 32]>, String> {
     let mut result = Vec::with_capacity(base58_strings.len());
 
     for base58_string in base58_strings {
-        // Decode from base58
         let bytes = bs58::decode(&base58_string)
             .into_vec()
-            .map_err(|e| format!("Error: Failed to decode the Base58 string. '{}': {}", base58_string, e))
-            .unwrap();
+            .map_err(|e| format!("Failed to decode the Base58 string '{}': {}", base58_string, e))?;
+
+        if bytes.len() != 32 {
+            return Err(format!(
+                "Expected a 32-byte hash, got {} bytes for '{}'",
+                bytes.len(),
+                base58_string
+            ));
+        }
 
-        // Convert to fixed-size array
         let mut array = [0u8; 32];
         array.copy_from_slice(&bytes);
 
         result.push(array);
     }
 
-    result
+    Ok(result)
 }
\ No newline at end of file
@@ -0,0 +1,91 @@
+use base64;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    address_lookup_table::{
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::AddressLookupTable,
+        AddressLookupTableAccount,
+    },
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+/// Fetches and deserializes the on-chain state for each lookup table address
+/// so it can be handed to `v0::Message::try_compile`.
+pub fn resolve_lookup_tables(
+    client: &RpcClient,
+    table_addresses: &[Pubkey],
+) -> Result<Vec<AddressLookupTableAccount>, String> {
+    table_addresses
+        .iter()
+        .map(|address| {
+            let raw_account = client
+                .get_account(address)
+                .map_err(|e| format!("Failed to fetch lookup table {}: {}", address, e))?;
+            let table = AddressLookupTable::deserialize(&raw_account.data)
+                .map_err(|e| format!("Failed to deserialize lookup table {}: {}", address, e))?;
+            Ok(AddressLookupTableAccount {
+                key: *address,
+                addresses: table.addresses.to_vec(),
+            })
+        })
+        .collect()
+}
+
+/// Builds, signs and base64-encodes a v0 versioned transaction, resolving
+/// the proof `AccountMeta`s against `lookup_tables` (table index + key
+/// lists) instead of inlining every 32-byte key. This is what lets a
+/// deep-proof transfer fit under the 1232-byte packet limit. `signers[0]`
+/// pays for and is the fee payer of the transaction.
+pub fn build_versioned_transaction(
+    client: &RpcClient,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<String, String> {
+    let payer = signers.first().ok_or("At least one signer is required")?;
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+
+    let message = v0::Message::try_compile(&payer.pubkey(), instructions, lookup_tables, recent_blockhash)
+        .map_err(|e| format!("Failed to compile v0 message: {}", e))?;
+
+    let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
+        .map_err(|e| format!("Failed to sign versioned transaction: {}", e))?;
+
+    let serialized_tx =
+        bincode::serialize(&tx).map_err(|e| format!("Failed to serialize versioned transaction: {}", e))?;
+    Ok(base64::encode(serialized_tx))
+}
+
+/// Builds the `create_lookup_table` + `extend_lookup_table` instructions for
+/// `addresses` so a caller can create the ALT a deep-proof transfer will
+/// reference, before building the versioned transaction itself.
+pub fn create_lookup_table_transaction(
+    client: &RpcClient,
+    payer: &Keypair,
+    recent_slot: u64,
+    addresses: Vec<Pubkey>,
+) -> Result<(String, Pubkey), String> {
+    let (create_ix, table_address) = create_lookup_table(payer.pubkey(), payer.pubkey(), recent_slot);
+    let extend_ix = extend_lookup_table(table_address, payer.pubkey(), Some(payer.pubkey()), addresses);
+
+    let recent_blockhash = client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to get recent blockhash: {}", e))?;
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ix, extend_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    let serialized_tx =
+        bincode::serialize(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    Ok((base64::encode(serialized_tx), table_address))
+}